@@ -1,8 +1,13 @@
-use num::{bigint::ToBigUint, BigUint};
-use std::{collections::BTreeMap, convert::TryInto};
-
-const BETA: i32 = 3;
-const GAMMA1: i32 = 9;
+use num::{
+    bigint::{ToBigInt, ToBigUint},
+    BigRational, BigUint, Signed, ToPrimitive, Zero,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    num::Wrapping,
+};
 
 // This computation counts the complete distribution of all possible
 // signatures in vanilla Dilithium and tweaked Dilithium.  We iterate
@@ -40,27 +45,92 @@ const GAMMA1: i32 = 9;
 //     but c != cp.  The RO is a function, so this cannot happen. We
 //     do not count this case.
 
-const ORD_B: i32 = 2 * BETA + 1;
-const ORD_B_SQ: i32 = ORD_B.pow(2);
-const ORD_Y: i32 = 2 * GAMMA1;
+// `beta` and `gamma1` used to be hard-coded constants, which meant
+// exploring a different Dilithium parameter set required editing and
+// recompiling this file.  They are now read from the command line (see
+// `parse_params`) and carried as `i64` throughout, since `(#B^2)^(#Y - 2)`
+// overflows `i32`/`u32` long before the `BigUint` it feeds even gets
+// built.
+//
+// `q` is the ring modulus `z = y + c*s1` is reduced under.  Real
+// Dilithium represents ring elements by their centered representative in
+// `(-q/2, q/2]`, so `z` can wrap around the modulus boundary instead of
+// growing without bound the way plain `i64` addition would suggest.
+#[derive(Debug, Clone, Copy)]
+struct Params {
+    beta: i64,
+    gamma1: i64,
+    q: i64,
+}
+
+impl Params {
+    // #B: the amount of possible challenge coefficient values.
+    fn ord_b(&self) -> i64 {
+        2 * self.beta + 1
+    }
 
-type CounterMap = BTreeMap<(i32, i32, i32, i32), num::BigUint>;
+    // #B^2: the amount of possible (c1, c2) challenge pairs.
+    fn ord_b_sq(&self) -> i64 {
+        self.ord_b() * self.ord_b()
+    }
 
-fn z_is_in_bounds(z: i32) -> bool {
-    z.abs() < GAMMA1 - BETA
+    // #Y: the order of the set of one y-coefficient.
+    fn ord_y(&self) -> i64 {
+        2 * self.gamma1
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        // beta=3, gamma1=9 are illustrative toy values; q is the actual
+        // Dilithium ring modulus.
+        Params {
+            beta: 3,
+            gamma1: 9,
+            q: 8_380_417,
+        }
+    }
+}
+
+// Maps `x` into its centered representative in `(-q/2, q/2]`.
+fn center_reduce(x: i64, q: i64) -> i64 {
+    let r = x.rem_euclid(q);
+    if r > q / 2 {
+        r - q
+    } else {
+        r
+    }
+}
+
+// Computes `a + b mod q`, wrapping-safe on the `i64` addition itself, and
+// returns the result centered in `(-q/2, q/2]`.
+fn mod_add(a: i64, b: i64, q: i64) -> i64 {
+    center_reduce((Wrapping(a) + Wrapping(b)).0, q)
+}
+
+type CounterMap = BTreeMap<(i64, i64, i64, i64), num::BigUint>;
+
+// Counter map used by the Monte-Carlo estimator: each accepted signature
+// is weighted by 1 (an actual draw) rather than by the amount of random
+// oracles consistent with it, so a plain `u64` suffices here.
+type SampleCounterMap = BTreeMap<(i64, i64, i64, i64), u64>;
+
+fn z_is_in_bounds(params: &Params, z: i64) -> bool {
+    z.abs() < params.gamma1 - params.beta
 }
 
 fn dilithium_ztrick(
-    y1: i32,
-    y2: i32,
-    y1p: i32,
-    y2p: i32,
-    only_iterations: Option<i32>,
+    params: &Params,
+    y1: i64,
+    y2: i64,
+    y1p: i64,
+    y2p: i64,
+    only_iterations: Option<i64>,
 ) -> CounterMap {
-    assert!(-GAMMA1 < y1 && y1 <= GAMMA1);
-    assert!(-GAMMA1 < y2 && y2 <= GAMMA1);
-    assert!(-GAMMA1 < y1p && y1p <= GAMMA1);
-    assert!(-GAMMA1 < y2p && y2p <= GAMMA1);
+    assert!(-params.gamma1 < y1 && y1 <= params.gamma1);
+    assert!(-params.gamma1 < y2 && y2 <= params.gamma1);
+    assert!(-params.gamma1 < y1p && y1p <= params.gamma1);
+    assert!(-params.gamma1 < y2p && y2p <= params.gamma1);
 
     let mut zc = BTreeMap::new();
     let mut count_signature = |z1, z2, c1, c2, v| {
@@ -72,34 +142,39 @@ fn dilithium_ztrick(
     // at most we will be doing two iterations.
     // We already know the values of the inputs, they are (y1, y2) and
     // (y1p, y2), so we only need to iterate over two challenge values.
-    for (c1, c2, cp1, cp2) in
-        itertools::iproduct!(-BETA..=BETA, -BETA..=BETA, -BETA..=BETA, -BETA..=BETA)
-    {
+    for (c1, c2, cp1, cp2) in itertools::iproduct!(
+        -params.beta..=params.beta,
+        -params.beta..=params.beta,
+        -params.beta..=params.beta,
+        -params.beta..=params.beta
+    ) {
         let mut iteration = 1;
         let mut c_used = (c1, c2);
-        let mut z1 = y1 + c1;
-        let mut z2 = y2 + c2;
-        if !z_is_in_bounds(z1) {
+        let mut z1 = mod_add(y1, c1, params.q);
+        let mut z2 = mod_add(y2, c2, params.q);
+        if !z_is_in_bounds(params, z1) {
             // z1 is not in bounds; resample y1.
             iteration += 1;
             c_used = (cp1, cp2);
-            z1 = y1p + cp1;
-            z2 = y2 + cp2;
-            if !z_is_in_bounds(z1) || !z_is_in_bounds(z2) {
+            z1 = mod_add(y1p, cp1, params.q);
+            z2 = mod_add(y2, cp2, params.q);
+            if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
                 // After two iterations, still no valid signature.
                 continue;
             }
-        } else if !z_is_in_bounds(z2) {
+        } else if !z_is_in_bounds(params, z2) {
             // We already saw z1, so both y1 and y2 will be resampled.
             iteration += 1;
-            z1 = y1p + cp1;
-            z2 = y2p + cp2;
+            c_used = (cp1, cp2);
+            z1 = mod_add(y1p, cp1, params.q);
+            z2 = mod_add(y2p, cp2, params.q);
         }
 
-        if !z_is_in_bounds(z1) || !z_is_in_bounds(z2) {
+        if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
             continue;
         }
 
+        #[allow(clippy::collapsible_if)]
         if let Some(it) = only_iterations {
             if iteration != it {
                 continue;
@@ -108,14 +183,18 @@ fn dilithium_ztrick(
 
         if (y1, y2) != (y1p, y2p) {
             // Case 1
-            let programmed = (ORD_Y - 2).try_into().unwrap();
-            let count = ORD_B_SQ.to_biguint().unwrap().pow(programmed);
+            let exponent: u32 = (params.ord_y() - 2)
+                .try_into()
+                .expect("ord_y - 2 exceeds u32");
+            let count = params.ord_b_sq().to_biguint().unwrap().pow(exponent);
             count_signature(z1, z2, c_used.0, c_used.1, count);
             continue;
         } else if (y1, y2) == (y1p, y2p) && (c1, c2) == (cp1, cp2) {
             // Case 2
-            let programmed = (ORD_Y - 1).try_into().unwrap();
-            let count = ORD_B_SQ.to_biguint().unwrap().pow(programmed);
+            let exponent: u32 = (params.ord_y() - 1)
+                .try_into()
+                .expect("ord_y - 1 exceeds u32");
+            let count = params.ord_b_sq().to_biguint().unwrap().pow(exponent);
             count_signature(z1, z2, c_used.0, c_used.1, count);
             continue;
         } else if (y1, y2) == (y1p, y2p) && (c1, c2) != (cp1, cp2) {
@@ -130,16 +209,17 @@ fn dilithium_ztrick(
 }
 
 fn dilithium_vanilla(
-    y1: i32,
-    y2: i32,
-    y1p: i32,
-    y2p: i32,
-    only_iterations: Option<i32>,
+    params: &Params,
+    y1: i64,
+    y2: i64,
+    y1p: i64,
+    y2p: i64,
+    only_iterations: Option<i64>,
 ) -> CounterMap {
-    assert!(-GAMMA1 < y1 && y1 <= GAMMA1);
-    assert!(-GAMMA1 < y2 && y2 <= GAMMA1);
-    assert!(-GAMMA1 < y1p && y1p <= GAMMA1);
-    assert!(-GAMMA1 < y2p && y2p <= GAMMA1);
+    assert!(-params.gamma1 < y1 && y1 <= params.gamma1);
+    assert!(-params.gamma1 < y2 && y2 <= params.gamma1);
+    assert!(-params.gamma1 < y1p && y1p <= params.gamma1);
+    assert!(-params.gamma1 < y2p && y2p <= params.gamma1);
 
     let mut zc = BTreeMap::new();
     let mut count_signature = |z1, z2, c1, c2, v: BigUint| {
@@ -151,25 +231,29 @@ fn dilithium_vanilla(
     // at most we will be doing two iterations.
     // We already know the values of the inputs, they are (y1, y2) and
     // (y1p, y2), so we only need to iterate over two challenge values.
-    for (c1, c2, cp1, cp2) in
-        itertools::iproduct!(-BETA..=BETA, -BETA..=BETA, -BETA..=BETA, -BETA..=BETA)
-    {
+    for (c1, c2, cp1, cp2) in itertools::iproduct!(
+        -params.beta..=params.beta,
+        -params.beta..=params.beta,
+        -params.beta..=params.beta,
+        -params.beta..=params.beta
+    ) {
         let mut iteration = 1;
         let mut c_used = (c1, c2);
-        let mut z1 = y1 + c1;
-        let mut z2 = y2 + c2;
-        if !z_is_in_bounds(z1) || !z_is_in_bounds(z2) {
+        let mut z1 = mod_add(y1, c1, params.q);
+        let mut z2 = mod_add(y2, c2, params.q);
+        if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
             iteration += 1;
             c_used = (cp1, cp2);
-            z1 = y1p + cp1;
-            z2 = y2p + cp2;
+            z1 = mod_add(y1p, cp1, params.q);
+            z2 = mod_add(y2p, cp2, params.q);
         }
 
-        if !z_is_in_bounds(z1) || !z_is_in_bounds(z2) {
+        if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
             // Second abort.
             continue;
         }
 
+        #[allow(clippy::collapsible_if)]
         if let Some(it) = only_iterations {
             if iteration != it {
                 continue;
@@ -178,13 +262,17 @@ fn dilithium_vanilla(
 
         if (y1, y2) != (y1p, y2p) {
             // Case 1.
-            let programmed = ORD_Y as u32 - 2;
-            let count = ORD_B_SQ.to_biguint().unwrap().pow(programmed);
+            let exponent: u32 = (params.ord_y() - 2)
+                .try_into()
+                .expect("ord_y - 2 exceeds u32");
+            let count = params.ord_b_sq().to_biguint().unwrap().pow(exponent);
             count_signature(z1, z2, c_used.0, c_used.1, count);
         } else if (y1, y2) == (y1p, y2p) && (c1, c2) == (cp1, cp2) {
             // Case 2.
-            let programmed = ORD_Y as u32 - 1;
-            let count = ORD_B_SQ.to_biguint().unwrap().pow(programmed);
+            let exponent: u32 = (params.ord_y() - 1)
+                .try_into()
+                .expect("ord_y - 1 exceeds u32");
+            let count = params.ord_b_sq().to_biguint().unwrap().pow(exponent);
             count_signature(z1, z2, c_used.0, c_used.1, count);
             continue;
         } else if (y1, y2) == (y1p, y2p) && (c1, c2) != (cp1, cp2) {
@@ -198,6 +286,110 @@ fn dilithium_vanilla(
     zc
 }
 
+// Draws one `(y1, y2, y1p, y2p, c1, c2, cp1, cp2)` tuple uniformly at
+// random and runs the same resample-on-abort iteration as
+// `dilithium_vanilla`, returning the accepted `(z1, z2, c1, c2)`, or
+// `None` if the second (and last) attempt also aborts.
+fn sample_vanilla<R: Rng>(params: &Params, rng: &mut R) -> Option<(i64, i64, i64, i64)> {
+    let y1 = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y2 = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y1p = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y2p = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let c1 = rng.gen_range(-params.beta..=params.beta);
+    let c2 = rng.gen_range(-params.beta..=params.beta);
+    let cp1 = rng.gen_range(-params.beta..=params.beta);
+    let cp2 = rng.gen_range(-params.beta..=params.beta);
+
+    let mut c_used = (c1, c2);
+    let mut z1 = mod_add(y1, c1, params.q);
+    let mut z2 = mod_add(y2, c2, params.q);
+    if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
+        c_used = (cp1, cp2);
+        z1 = mod_add(y1p, cp1, params.q);
+        z2 = mod_add(y2p, cp2, params.q);
+    }
+
+    if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
+        return None;
+    }
+
+    Some((z1, z2, c_used.0, c_used.1))
+}
+
+// Same as `sample_vanilla`, but mirrors the z-trick's iteration order:
+// a failing `z1` resamples only `y1`/`c` (keeping `y2` from the first
+// attempt), while a failing `z2` after a passing `z1` resamples both
+// coordinates.
+fn sample_ztrick<R: Rng>(params: &Params, rng: &mut R) -> Option<(i64, i64, i64, i64)> {
+    let y1 = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y2 = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y1p = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let y2p = rng.gen_range(-params.gamma1 + 1..=params.gamma1);
+    let c1 = rng.gen_range(-params.beta..=params.beta);
+    let c2 = rng.gen_range(-params.beta..=params.beta);
+    let cp1 = rng.gen_range(-params.beta..=params.beta);
+    let cp2 = rng.gen_range(-params.beta..=params.beta);
+
+    let mut c_used = (c1, c2);
+    let mut z1 = mod_add(y1, c1, params.q);
+    let mut z2 = mod_add(y2, c2, params.q);
+    if !z_is_in_bounds(params, z1) {
+        c_used = (cp1, cp2);
+        z1 = mod_add(y1p, cp1, params.q);
+        z2 = mod_add(y2, cp2, params.q);
+        if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
+            return None;
+        }
+    } else if !z_is_in_bounds(params, z2) {
+        c_used = (cp1, cp2);
+        z1 = mod_add(y1p, cp1, params.q);
+        z2 = mod_add(y2p, cp2, params.q);
+    }
+
+    if !z_is_in_bounds(params, z1) || !z_is_in_bounds(params, z2) {
+        return None;
+    }
+
+    Some((z1, z2, c_used.0, c_used.1))
+}
+
+// Empirically estimates the vanilla and z-trick signature distributions
+// by drawing `samples` independent signing attempts from a seeded RNG.
+// Unlike the exhaustive enumeration, this scales to realistic parameter
+// sizes where `ord_b_sq().pow(ord_y() - 2)` would be intractable.
+fn monte_carlo_estimate(
+    params: &Params,
+    samples: u64,
+    seed: u64,
+) -> (SampleCounterMap, SampleCounterMap) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut vanilla_results = SampleCounterMap::new();
+    let mut ztrick_results = SampleCounterMap::new();
+
+    for _ in 0..samples {
+        if let Some(k) = sample_vanilla(params, &mut rng) {
+            *vanilla_results.entry(k).or_default() += 1;
+        }
+        if let Some(k) = sample_ztrick(params, &mut rng) {
+            *ztrick_results.entry(k).or_default() += 1;
+        }
+    }
+
+    (vanilla_results, ztrick_results)
+}
+
+// Prints the empirical signature frequencies from a Monte-Carlo run,
+// normalized by the number of accepted draws so they can be compared
+// directly against the theoretical uniform target of `1 / #keys`.
+fn report_monte_carlo(label: &str, results: &SampleCounterMap, samples: u64) {
+    eprintln!("{} (Monte-Carlo, {} samples):", label, samples);
+    let accepted: u64 = results.values().sum();
+    for (k, count) in results.iter() {
+        let freq = *count as f64 / accepted as f64;
+        eprintln!("{:?}: {} ({:.6})", k, count, freq);
+    }
+}
+
 fn is_uniform<I, K, V>(iter: &mut I) -> bool
 where
     I: Iterator<Item = (K, V)>,
@@ -209,7 +401,7 @@ where
     } else {
         return true;
     };
-    while let Some((k, v)) = iter.next() {
+    for (k, v) in iter.by_ref() {
         if v != baseline {
             eprintln!(
                 "value at {:?} ({:?}) is not same as baseline ({:?})",
@@ -228,27 +420,188 @@ fn merge_results(map: &mut CounterMap, other: &CounterMap) {
     }
 }
 
-fn main() {
-    let mut vanilla_results = BTreeMap::<_, BigUint>::new();
-    for (y1, y2, y1p, y2p) in itertools::iproduct!(
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1)
-    ) {
-        merge_results(&mut vanilla_results, &dilithium_vanilla(y1, y2, y1p, y2p, None));
+// A `CounterMap` normalized to an exact probability distribution: each
+// `BigUint` count divided by the total count, as a `BigRational` (which
+// keeps itself reduced by GCD on construction).
+type ProbMap = BTreeMap<(i64, i64, i64, i64), BigRational>;
+
+fn normalize(map: &CounterMap) -> ProbMap {
+    let total: BigUint = map.values().sum();
+    map.iter()
+        .map(|(k, v)| {
+            let p = BigRational::new(v.to_bigint().unwrap(), total.to_bigint().unwrap());
+            (*k, p)
+        })
+        .collect()
+}
+
+// The theoretical uniform target over the same key set as `map`: every
+// key gets probability `1 / #keys`.
+fn uniform_distribution(map: &CounterMap) -> ProbMap {
+    let uniform_prob = BigRational::new(
+        1.to_bigint().unwrap(),
+        (map.len() as u64).to_bigint().unwrap(),
+    );
+    map.keys().map(|k| (*k, uniform_prob.clone())).collect()
+}
+
+// Total variation distance `1/2 * sum_k |p(k) - q(k)|` between two
+// distributions, computed exactly over their key union (a key missing
+// from one side contributes 0 for that side).
+fn total_variation_distance(p: &ProbMap, q: &ProbMap) -> BigRational {
+    let keys: BTreeSet<_> = p.keys().chain(q.keys()).collect();
+    let mut sum = BigRational::zero();
+    for k in keys {
+        let pv = p.get(k).cloned().unwrap_or_else(BigRational::zero);
+        let qv = q.get(k).cloned().unwrap_or_else(BigRational::zero);
+        sum += (pv - qv).abs();
     }
+    sum / BigRational::from_integer(2.to_bigint().unwrap())
+}
 
-    let mut ztrick_results = BTreeMap::new();
-    for (y1, y2, y1p, y2p) in itertools::iproduct!(
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1),
-        (-GAMMA1 + 1..=GAMMA1)
-    ) {
-        merge_results(&mut ztrick_results, &dilithium_ztrick(y1, y2, y1p, y2p, None));
+// The largest pointwise ratio between two distributions, in whichever
+// direction is >= 1, so it reads as "how many times more likely is the
+// most over/under-represented key". Keys where either side is zero are
+// skipped, since the ratio is unbounded there.
+fn max_pointwise_ratio(p: &ProbMap, q: &ProbMap) -> BigRational {
+    let keys: BTreeSet<_> = p.keys().chain(q.keys()).collect();
+    let mut max_ratio = BigRational::zero();
+    for k in keys {
+        let pv = p.get(k).cloned().unwrap_or_else(BigRational::zero);
+        let qv = q.get(k).cloned().unwrap_or_else(BigRational::zero);
+        if pv.is_zero() || qv.is_zero() {
+            continue;
+        }
+        let ratio = if pv >= qv { &pv / &qv } else { &qv / &pv };
+        if ratio > max_ratio {
+            max_ratio = ratio;
+        }
+    }
+    max_ratio
+}
+
+// Reports the total variation distance and max pointwise ratio between
+// two distributions, both as exact rationals and as their `f64`
+// approximations for quick reading.
+fn report_distance(label: &str, p: &ProbMap, q: &ProbMap) {
+    let tvd = total_variation_distance(p, q);
+    let max_ratio = max_pointwise_ratio(p, q);
+    eprintln!(
+        "{}: total variation distance = {} (~{:.6}), max pointwise ratio = {} (~{:.6})",
+        label,
+        tvd,
+        tvd.to_f64().unwrap_or(f64::NAN),
+        max_ratio,
+        max_ratio.to_f64().unwrap_or(f64::NAN),
+    );
+}
+
+// Drives one of the `dilithium_{vanilla,ztrick}` passes across all worker
+// threads and folds the results together.  The outer `y1` range is split
+// into one contiguous chunk per worker (modeled on bellman's
+// `multicore::Worker`/`parallel_fft` split), each worker accumulates its
+// own `CounterMap`, and the per-worker maps are folded into the final
+// result via `merge_results`.  Chunks are folded back in the same order
+// they were spawned in, so the output is deterministic across runs.
+fn enumerate_parallel(
+    params: Params,
+    method: fn(&Params, i64, i64, i64, i64, Option<i64>) -> CounterMap,
+    threads: usize,
+) -> CounterMap {
+    let threads = threads.max(1);
+    let y1_range: Vec<i64> = (-params.gamma1 + 1..=params.gamma1).collect();
+    let chunk_size = y1_range.len().div_ceil(threads);
+
+    let handles: Vec<_> = y1_range
+        .chunks(chunk_size.max(1))
+        .map(|chunk| {
+            let chunk = chunk.to_vec();
+            std::thread::spawn(move || {
+                let mut local_results = CounterMap::new();
+                for y1 in chunk {
+                    for (y2, y1p, y2p) in itertools::iproduct!(
+                        (-params.gamma1 + 1..=params.gamma1),
+                        (-params.gamma1 + 1..=params.gamma1),
+                        (-params.gamma1 + 1..=params.gamma1)
+                    ) {
+                        merge_results(&mut local_results, &method(&params, y1, y2, y1p, y2p, None));
+                    }
+                }
+                local_results
+            })
+        })
+        .collect();
+
+    let mut results = CounterMap::new();
+    for handle in handles {
+        let local_results = handle.join().expect("worker thread panicked");
+        merge_results(&mut results, &local_results);
+    }
+    results
+}
+
+// Parses `--beta <N> --gamma1 <N> --q <N>` off the command line, falling
+// back to `Params::default()` for any flag that is missing.
+fn parse_params() -> Params {
+    let args: Vec<String> = std::env::args().collect();
+    let default = Params::default();
+    let beta = args
+        .iter()
+        .position(|a| a == "--beta")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default.beta);
+    let gamma1 = args
+        .iter()
+        .position(|a| a == "--gamma1")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default.gamma1);
+    let q = args
+        .iter()
+        .position(|a| a == "--q")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default.q);
+    Params { beta, gamma1, q }
+}
+
+// Parses `--sample <N> [--seed <S>]` off the command line.  `--sample`
+// switches `main` into the Monte-Carlo estimator; `--seed` defaults to 0
+// so a bare `--sample <N>` is still reproducible.
+fn parse_monte_carlo_args() -> Option<(u64, u64)> {
+    let args: Vec<String> = std::env::args().collect();
+    let samples = args
+        .iter()
+        .position(|a| a == "--sample")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())?;
+    let seed = args
+        .iter()
+        .position(|a| a == "--seed")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    Some((samples, seed))
+}
+
+fn main() {
+    let params = parse_params();
+
+    if let Some((samples, seed)) = parse_monte_carlo_args() {
+        let (vanilla_results, ztrick_results) = monte_carlo_estimate(&params, samples, seed);
+        report_monte_carlo("vanilla", &vanilla_results, samples);
+        report_monte_carlo("ztrick", &ztrick_results, samples);
+        return;
     }
 
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let vanilla_results = enumerate_parallel(params, dilithium_vanilla, threads);
+    let ztrick_results = enumerate_parallel(params, dilithium_ztrick, threads);
+
     eprintln!("vanilla:");
     dbg!(is_uniform(&mut vanilla_results.iter()));
 
@@ -261,12 +614,31 @@ fn main() {
         eprintln!(
             "{:?}: {}, {}",
             k,
-            vanilla_results.get(k).map(|x| x).unwrap(),
-            ztrick_results.get(k).map(|x| x).unwrap()
+            vanilla_results.get(k).unwrap(),
+            ztrick_results.get(k).unwrap()
         );
     }
 
-    //     dbg!(GAMMA1, BETA);
+    // Quantify how far each distribution is from uniform, and how far the
+    // z-trick drifts from vanilla, rather than only knowing *that* they
+    // differ.
+    let vanilla_dist = normalize(&vanilla_results);
+    let ztrick_dist = normalize(&ztrick_results);
+
+    eprintln!("distributional bias:");
+    report_distance(
+        "vanilla vs uniform",
+        &vanilla_dist,
+        &uniform_distribution(&vanilla_results),
+    );
+    report_distance(
+        "ztrick vs uniform",
+        &ztrick_dist,
+        &uniform_distribution(&ztrick_results),
+    );
+    report_distance("vanilla vs ztrick", &vanilla_dist, &ztrick_dist);
+
+    //     dbg!(params.gamma1, params.beta);
     //     let mut ratios: Vec<_> = ztrick_results.values().cloned().collect();
     //     let mut gcd = ratios[0].to_owned();
     //     for n in ratios[1..].iter() {
@@ -274,8 +646,8 @@ fn main() {
     //     }
 
     //     // Divide by the amount of hash functions when two inputs have been set.
-    //     let programmed = (ORD_Y * ORD_Y) as u32 - 2;
-    //     let normalize = ORD_B.to_biguint().unwrap().pow(programmed);
+    //     let programmed = (params.ord_y() * params.ord_y()) as u32 - 2;
+    //     let normalize = params.ord_b().to_biguint().unwrap().pow(programmed);
     //     dbg!(gcd == normalize);
 
     //     for n in &mut ratios.iter_mut() {